@@ -1,10 +1,15 @@
-use std::env;
-use std::path::PathBuf;
-
 fn main() {
     // Tell Cargo to rerun this build script if the schema file changes
     println!("cargo:rerun-if-changed=schema.graphql");
-    
-    // Optional: Download schema if not present (you might need to do this manually)
-    // For now, we'll use a simplified approach without schema validation
-}
\ No newline at end of file
+
+    // Parse and register schema.graphql with cynic-codegen as the crate's
+    // default schema. A typo'd or structurally invalid schema fails the
+    // build right here with a parser error, instead of surfacing later as
+    // a confusing error from the `QueryFragment`/`QueryVariables` derives
+    // in `src/queries.rs`.
+    cynic_codegen::register_schema("sui")
+        .from_sdl_file("schema.graphql")
+        .expect("schema.graphql should exist and be valid SDL")
+        .as_default()
+        .expect("failed to register schema.graphql as the default schema");
+}
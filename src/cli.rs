@@ -0,0 +1,70 @@
+//! Command-line surface: network selection and the `fetch` / `diff`
+//! subcommands.
+
+use clap::{Parser, Subcommand, ValueEnum};
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Network {
+    Mainnet,
+    Testnet,
+    Devnet,
+}
+
+impl Network {
+    /// The GraphQL endpoint Sui serves for this network.
+    pub fn endpoint(self) -> &'static str {
+        match self {
+            Network::Mainnet => "https://sui-mainnet.mystenlabs.com/graphql",
+            Network::Testnet => "https://sui-testnet.mystenlabs.com/graphql",
+            Network::Devnet => "https://sui-devnet.mystenlabs.com/graphql",
+        }
+    }
+}
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "package_diff",
+    about = "Fetch and diff Sui Move packages across on-chain versions"
+)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+
+    /// Network to query. Ignored if `--endpoint` is also set.
+    #[arg(long, value_enum, default_value_t = Network::Mainnet, global = true)]
+    pub network: Network,
+
+    /// Explicit GraphQL endpoint, overriding `--network`.
+    #[arg(long, global = true)]
+    pub endpoint: Option<String>,
+}
+
+impl Cli {
+    /// The endpoint this invocation should hit: `--endpoint` if given,
+    /// otherwise whatever `--network` maps to.
+    pub fn resolved_endpoint(&self) -> String {
+        self.endpoint
+            .clone()
+            .unwrap_or_else(|| self.network.endpoint().to_string())
+    }
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Fetch a package and all its historical versions, writing response.json.
+    Fetch {
+        /// `0x...` address or `name@org[/version]` Move Registry name.
+        address_or_name: String,
+    },
+    /// Diff two versions of the same package.
+    Diff {
+        /// `0x...` address or `name@org` Move Registry name.
+        address_or_name: String,
+        /// The earlier version to diff from.
+        #[arg(long)]
+        from: u64,
+        /// The later version to diff to.
+        #[arg(long)]
+        to: u64,
+    },
+}
@@ -0,0 +1,250 @@
+//! Decode base64 BCS payloads (the Sui GraphQL `packageBcs` / `moduleBcs`
+//! fields) into structured Move modules, mirroring what the Sui SDK's
+//! `package()` helper does with `Base64::decode_vec` before handing bytes
+//! to `move-binary-format`.
+
+use anyhow::{Context, Result};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use move_binary_format::file_format::{
+    Ability, AbilitySet, SignatureToken, StructFieldInformation, Visibility,
+};
+use move_binary_format::CompiledModule;
+
+use crate::queries::MovePackageVersion;
+
+#[derive(Debug, Clone)]
+pub struct DecodedField {
+    pub name: String,
+    pub type_: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct DecodedStruct {
+    pub name: String,
+    pub abilities: Vec<String>,
+    pub fields: Vec<DecodedField>,
+}
+
+/// A function type parameter and the ability constraints it's declared
+/// with (e.g. the `T: copy + drop` in `fun foo<T: copy + drop>(...)`), kept
+/// so that narrowing a constraint set in an upgrade can be told apart from
+/// an unrelated signature change.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedTypeParam {
+    pub name: String,
+    pub constraints: Vec<String>,
+}
+
+impl DecodedTypeParam {
+    pub fn rendered(&self) -> String {
+        if self.constraints.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{}: {}", self.name, self.constraints.join(" + "))
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DecodedFunction {
+    pub name: String,
+    pub visibility: String,
+    pub is_entry: bool,
+    pub type_params: Vec<DecodedTypeParam>,
+    pub parameters: Vec<String>,
+    pub returns: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DecodedModule {
+    pub name: String,
+    pub structs: Vec<DecodedStruct>,
+    pub functions: Vec<DecodedFunction>,
+    pub friends: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DecodedPackage {
+    pub address: String,
+    pub version: i32,
+    pub modules: Vec<DecodedModule>,
+}
+
+/// Decode one historical version of a package (its per-module BCS) into a
+/// [`DecodedPackage`]. `address`/`version` come from the GraphQL node since
+/// the on-chain package BCS itself doesn't repeat them per module.
+pub fn decode_package_version(
+    address: &str,
+    version: i32,
+    node: &MovePackageVersion,
+) -> Result<DecodedPackage> {
+    let module_bcs = node
+        .module_bcs
+        .as_ref()
+        .context("package version has no module BCS to decode")?;
+
+    let modules = module_bcs
+        .iter()
+        .map(|encoded| decode_module(&encoded.0))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(DecodedPackage {
+        address: address.to_string(),
+        version,
+        modules,
+    })
+}
+
+fn decode_module(base64_bcs: &str) -> Result<DecodedModule> {
+    let bytes = BASE64
+        .decode(base64_bcs)
+        .context("moduleBcs field is not valid base64")?;
+    let module =
+        CompiledModule::deserialize(&bytes).context("failed to BCS-deserialize CompiledModule")?;
+
+    let name = module.identifier_at(module.self_handle().name).to_string();
+
+    let structs = module
+        .struct_defs()
+        .iter()
+        .map(|def| decode_struct(&module, def))
+        .collect();
+
+    let functions = module
+        .function_defs()
+        .iter()
+        .map(|def| decode_function(&module, def))
+        .collect();
+
+    let friends = module
+        .friend_decls()
+        .iter()
+        .map(|handle| module.identifier_at(handle.name).to_string())
+        .collect();
+
+    Ok(DecodedModule {
+        name,
+        structs,
+        functions,
+        friends,
+    })
+}
+
+fn decode_struct(
+    module: &CompiledModule,
+    def: &move_binary_format::file_format::StructDefinition,
+) -> DecodedStruct {
+    let handle = module.datatype_handle_at(def.struct_handle);
+    let name = module.identifier_at(handle.name).to_string();
+    let abilities = handle
+        .abilities
+        .into_iter()
+        .map(ability_name)
+        .map(str::to_string)
+        .collect();
+
+    let fields = match &def.field_information {
+        StructFieldInformation::Declared(fields) => fields
+            .iter()
+            .map(|field| DecodedField {
+                name: module.identifier_at(field.name).to_string(),
+                type_: type_name(module, &field.signature.0),
+            })
+            .collect(),
+        StructFieldInformation::Native => Vec::new(),
+    };
+
+    DecodedStruct {
+        name,
+        abilities,
+        fields,
+    }
+}
+
+fn decode_function(
+    module: &CompiledModule,
+    def: &move_binary_format::file_format::FunctionDefinition,
+) -> DecodedFunction {
+    let handle = module.function_handle_at(def.function);
+    let name = module.identifier_at(handle.name).to_string();
+
+    let parameters = module
+        .signature_at(handle.parameters)
+        .0
+        .iter()
+        .map(|token| type_name(module, token))
+        .collect();
+    let returns = module
+        .signature_at(handle.return_)
+        .0
+        .iter()
+        .map(|token| type_name(module, token))
+        .collect();
+    let type_params = handle
+        .type_parameters
+        .iter()
+        .enumerate()
+        .map(|(i, constraints)| DecodedTypeParam {
+            name: format!("T{i}"),
+            constraints: ability_set_names(*constraints),
+        })
+        .collect();
+
+    DecodedFunction {
+        name,
+        visibility: visibility_name(def.visibility).to_string(),
+        is_entry: def.is_entry,
+        type_params,
+        parameters,
+        returns,
+    }
+}
+
+fn ability_set_names(abilities: AbilitySet) -> Vec<String> {
+    abilities.into_iter().map(ability_name).map(str::to_string).collect()
+}
+
+fn ability_name(ability: Ability) -> &'static str {
+    match ability {
+        Ability::Copy => "copy",
+        Ability::Drop => "drop",
+        Ability::Store => "store",
+        Ability::Key => "key",
+    }
+}
+
+fn visibility_name(visibility: Visibility) -> &'static str {
+    match visibility {
+        Visibility::Private => "private",
+        Visibility::Public => "public",
+        Visibility::Friend => "public(friend)",
+    }
+}
+
+fn type_name(module: &CompiledModule, token: &SignatureToken) -> String {
+    match token {
+        SignatureToken::Bool => "bool".to_string(),
+        SignatureToken::U8 => "u8".to_string(),
+        SignatureToken::U16 => "u16".to_string(),
+        SignatureToken::U32 => "u32".to_string(),
+        SignatureToken::U64 => "u64".to_string(),
+        SignatureToken::U128 => "u128".to_string(),
+        SignatureToken::U256 => "u256".to_string(),
+        SignatureToken::Address => "address".to_string(),
+        SignatureToken::Signer => "signer".to_string(),
+        SignatureToken::Vector(inner) => format!("vector<{}>", type_name(module, inner)),
+        SignatureToken::Datatype(handle_idx) => {
+            module.identifier_at(module.datatype_handle_at(*handle_idx).name).to_string()
+        }
+        SignatureToken::DatatypeInstantiation(inst) => {
+            let (handle_idx, type_args) = inst.as_ref();
+            let base = module.identifier_at(module.datatype_handle_at(*handle_idx).name);
+            let args: Vec<_> = type_args.iter().map(|t| type_name(module, t)).collect();
+            format!("{base}<{}>", args.join(", "))
+        }
+        SignatureToken::Reference(inner) => format!("&{}", type_name(module, inner)),
+        SignatureToken::MutableReference(inner) => format!("&mut {}", type_name(module, inner)),
+        SignatureToken::TypeParameter(idx) => format!("T{idx}"),
+    }
+}
@@ -0,0 +1,526 @@
+//! Structured, compatibility-aware diff between two [`DecodedPackage`]
+//! versions — the thing this crate is actually named for.
+//!
+//! Deltas are classified per Move's upgrade compatibility rules: adding a
+//! module, adding a private/new function, or widening visibility downward
+//! is `Compatible`; removing or changing the signature of anything public
+//! (or public(friend)) a caller could already depend on is `Breaking`.
+
+use std::collections::BTreeMap;
+
+use crate::decode::{DecodedFunction, DecodedModule, DecodedPackage, DecodedStruct, DecodedTypeParam};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compatibility {
+    Compatible,
+    Breaking,
+}
+
+impl Compatibility {
+    fn worst(self, other: Compatibility) -> Compatibility {
+        if self == Compatibility::Breaking || other == Compatibility::Breaking {
+            Compatibility::Breaking
+        } else {
+            Compatibility::Compatible
+        }
+    }
+}
+
+/// A function that was added, removed, or whose signature/visibility changed.
+#[derive(Debug, Clone)]
+pub struct FunctionChange {
+    pub name: String,
+    pub description: String,
+    pub compatibility: Compatibility,
+}
+
+/// A struct whose field list or ability set changed.
+#[derive(Debug, Clone)]
+pub struct StructChange {
+    pub name: String,
+    pub description: String,
+    pub compatibility: Compatibility,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ModuleDiff {
+    pub name: String,
+    pub function_changes: Vec<FunctionChange>,
+    pub struct_changes: Vec<StructChange>,
+    pub friend_changes: Vec<String>,
+}
+
+impl ModuleDiff {
+    fn compatibility(&self) -> Compatibility {
+        self.function_changes
+            .iter()
+            .map(|c| c.compatibility)
+            .chain(self.struct_changes.iter().map(|c| c.compatibility))
+            .fold(Compatibility::Compatible, Compatibility::worst)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.function_changes.is_empty() && self.struct_changes.is_empty() && self.friend_changes.is_empty()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PackageDiff {
+    pub added_modules: Vec<String>,
+    pub removed_modules: Vec<String>,
+    pub module_diffs: Vec<ModuleDiff>,
+    pub compatibility: Compatibility,
+}
+
+fn by_name<T>(items: &[T], name: impl Fn(&T) -> &str) -> BTreeMap<&str, &T> {
+    items.iter().map(|item| (name(item), item)).collect()
+}
+
+fn type_params_signature(type_params: &[DecodedTypeParam]) -> String {
+    type_params.iter().map(DecodedTypeParam::rendered).collect::<Vec<_>>().join(", ")
+}
+
+/// Orders visibilities from least to most accessible so a transition can be
+/// told apart as narrowing vs. widening, not just "changed".
+fn visibility_rank(visibility: &str) -> u8 {
+    match visibility {
+        "private" => 0,
+        "public(friend)" => 1,
+        _ => 2, // "public"
+    }
+}
+
+fn function_signature(f: &DecodedFunction) -> String {
+    format!(
+        "{}fun {}<{}>({}) -> ({})",
+        if f.is_entry { "entry " } else { "" },
+        f.visibility,
+        type_params_signature(&f.type_params),
+        f.parameters.join(", "),
+        f.returns.join(", ")
+    )
+}
+
+/// New private functions and new modules are always compatible; anything
+/// that removes or narrows a previously-public surface is breaking.
+fn diff_functions(old: &[DecodedFunction], new: &[DecodedFunction]) -> Vec<FunctionChange> {
+    let old_by_name = by_name(old, |f| f.name.as_str());
+    let new_by_name = by_name(new, |f| f.name.as_str());
+    let mut changes = Vec::new();
+
+    for (name, old_fn) in &old_by_name {
+        match new_by_name.get(name) {
+            None => {
+                let compatibility = if old_fn.visibility == "private" {
+                    Compatibility::Compatible
+                } else {
+                    Compatibility::Breaking
+                };
+                changes.push(FunctionChange {
+                    name: name.to_string(),
+                    description: format!("removed `{}`", function_signature(old_fn)),
+                    compatibility,
+                });
+            }
+            Some(new_fn) => {
+                let old_sig = function_signature(old_fn);
+                let new_sig = function_signature(new_fn);
+                if old_sig != new_sig {
+                    let visibility_narrowed =
+                        visibility_rank(&new_fn.visibility) < visibility_rank(&old_fn.visibility);
+                    // Entry is a transaction-callable flag independent of
+                    // visibility; dropping it strands any caller invoking
+                    // this function directly as a transaction.
+                    let entry_removed = old_fn.is_entry && !new_fn.is_entry;
+                    let signature_changed = old_fn.parameters != new_fn.parameters
+                        || old_fn.returns != new_fn.returns
+                        || old_fn.type_params != new_fn.type_params;
+                    let compatibility = if visibility_narrowed
+                        || entry_removed
+                        || (old_fn.visibility != "private" && signature_changed)
+                    {
+                        Compatibility::Breaking
+                    } else {
+                        Compatibility::Compatible
+                    };
+                    changes.push(FunctionChange {
+                        name: name.to_string(),
+                        description: format!("`{old_sig}` -> `{new_sig}`"),
+                        compatibility,
+                    });
+                }
+            }
+        }
+    }
+
+    for (name, new_fn) in &new_by_name {
+        if !old_by_name.contains_key(name) {
+            changes.push(FunctionChange {
+                name: name.to_string(),
+                description: format!("added `{}`", function_signature(new_fn)),
+                compatibility: Compatibility::Compatible,
+            });
+        }
+    }
+
+    changes
+}
+
+/// Struct layout changes (added/removed/reordered fields, changed
+/// abilities) are breaking: existing callers may have serialized data or
+/// generated code assuming the old layout.
+fn diff_structs(old: &[DecodedStruct], new: &[DecodedStruct]) -> Vec<StructChange> {
+    let old_by_name = by_name(old, |s| s.name.as_str());
+    let new_by_name = by_name(new, |s| s.name.as_str());
+    let mut changes = Vec::new();
+
+    for (name, old_struct) in &old_by_name {
+        match new_by_name.get(name) {
+            None => changes.push(StructChange {
+                name: name.to_string(),
+                description: "removed".to_string(),
+                compatibility: Compatibility::Breaking,
+            }),
+            Some(new_struct) => {
+                // Compare (name, type) pairs, not just names: a field that
+                // keeps its name but changes type is still a breaking
+                // layout change for anyone holding a reference to the old
+                // struct shape.
+                let old_fields: Vec<(&str, &str)> = old_struct
+                    .fields
+                    .iter()
+                    .map(|f| (f.name.as_str(), f.type_.as_str()))
+                    .collect();
+                let new_fields: Vec<(&str, &str)> = new_struct
+                    .fields
+                    .iter()
+                    .map(|f| (f.name.as_str(), f.type_.as_str()))
+                    .collect();
+                let old_field_names: Vec<_> = old_fields.iter().map(|(name, _)| *name).collect();
+                let new_field_names: Vec<_> = new_fields.iter().map(|(name, _)| *name).collect();
+                let old_abilities: Vec<_> = old_struct.abilities.clone();
+                let new_abilities: Vec<_> = new_struct.abilities.clone();
+
+                if old_fields != new_fields || old_abilities != new_abilities {
+                    let mut parts = Vec::new();
+                    let added: Vec<_> = new_field_names
+                        .iter()
+                        .filter(|f| !old_field_names.contains(f))
+                        .collect();
+                    let removed: Vec<_> = old_field_names
+                        .iter()
+                        .filter(|f| !new_field_names.contains(f))
+                        .collect();
+                    let changed_types: Vec<_> = old_fields
+                        .iter()
+                        .filter_map(|(field_name, old_ty)| {
+                            new_fields.iter().find_map(|(new_name, new_ty)| {
+                                (new_name == field_name && new_ty != old_ty)
+                                    .then(|| format!("{field_name}: {old_ty} -> {new_ty}"))
+                            })
+                        })
+                        .collect();
+                    if !added.is_empty() {
+                        parts.push(format!("added fields {added:?}"));
+                    }
+                    if !removed.is_empty() {
+                        parts.push(format!("removed fields {removed:?}"));
+                    }
+                    if !changed_types.is_empty() {
+                        parts.push(format!("changed field types [{}]", changed_types.join(", ")));
+                    }
+                    if added.is_empty()
+                        && removed.is_empty()
+                        && changed_types.is_empty()
+                        && old_field_names != new_field_names
+                    {
+                        parts.push("reordered fields".to_string());
+                    }
+                    if old_abilities != new_abilities {
+                        parts.push(format!("abilities {old_abilities:?} -> {new_abilities:?}"));
+                    }
+                    changes.push(StructChange {
+                        name: name.to_string(),
+                        description: parts.join(", "),
+                        compatibility: Compatibility::Breaking,
+                    });
+                }
+            }
+        }
+    }
+
+    for name in new_by_name.keys() {
+        if !old_by_name.contains_key(name) {
+            changes.push(StructChange {
+                name: name.to_string(),
+                description: "added".to_string(),
+                compatibility: Compatibility::Compatible,
+            });
+        }
+    }
+
+    changes
+}
+
+fn diff_friends(old: &DecodedModule, new: &DecodedModule) -> Vec<String> {
+    let mut changes = Vec::new();
+    for friend in &old.friends {
+        if !new.friends.contains(friend) {
+            changes.push(format!("removed friend `{friend}`"));
+        }
+    }
+    for friend in &new.friends {
+        if !old.friends.contains(friend) {
+            changes.push(format!("added friend `{friend}`"));
+        }
+    }
+    changes
+}
+
+fn diff_module(old: &DecodedModule, new: &DecodedModule) -> ModuleDiff {
+    ModuleDiff {
+        name: new.name.clone(),
+        function_changes: diff_functions(&old.functions, &new.functions),
+        struct_changes: diff_structs(&old.structs, &new.structs),
+        friend_changes: diff_friends(old, new),
+    }
+}
+
+/// Compare two decoded versions of the same package, module by module.
+pub fn diff_packages(old: &DecodedPackage, new: &DecodedPackage) -> PackageDiff {
+    let old_by_name = by_name(&old.modules, |m| m.name.as_str());
+    let new_by_name = by_name(&new.modules, |m| m.name.as_str());
+
+    let removed_modules: Vec<String> = old_by_name
+        .keys()
+        .filter(|name| !new_by_name.contains_key(*name))
+        .map(|name| name.to_string())
+        .collect();
+    let added_modules: Vec<String> = new_by_name
+        .keys()
+        .filter(|name| !old_by_name.contains_key(*name))
+        .map(|name| name.to_string())
+        .collect();
+
+    let module_diffs: Vec<ModuleDiff> = old_by_name
+        .iter()
+        .filter_map(|(name, old_module)| {
+            new_by_name
+                .get(name)
+                .map(|new_module| diff_module(old_module, new_module))
+        })
+        .filter(|diff| !diff.is_empty())
+        .collect();
+
+    // Removing a module entirely is breaking for anyone depending on it;
+    // adding one is purely additive.
+    let compatibility = module_diffs
+        .iter()
+        .map(ModuleDiff::compatibility)
+        .fold(Compatibility::Compatible, Compatibility::worst);
+    let compatibility = if removed_modules.is_empty() {
+        compatibility
+    } else {
+        Compatibility::Breaking
+    };
+
+    PackageDiff {
+        added_modules,
+        removed_modules,
+        module_diffs,
+        compatibility,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decode::{DecodedField, DecodedFunction, DecodedModule, DecodedPackage, DecodedStruct};
+
+    fn package(modules: Vec<DecodedModule>) -> DecodedPackage {
+        DecodedPackage {
+            address: "0x1".to_string(),
+            version: 1,
+            modules,
+        }
+    }
+
+    fn module(name: &str) -> DecodedModule {
+        DecodedModule {
+            name: name.to_string(),
+            structs: Vec::new(),
+            functions: Vec::new(),
+            friends: Vec::new(),
+        }
+    }
+
+    fn function(name: &str, visibility: &str, is_entry: bool) -> DecodedFunction {
+        DecodedFunction {
+            name: name.to_string(),
+            visibility: visibility.to_string(),
+            is_entry,
+            type_params: Vec::new(),
+            parameters: Vec::new(),
+            returns: Vec::new(),
+        }
+    }
+
+    fn field(name: &str, type_: &str) -> DecodedField {
+        DecodedField {
+            name: name.to_string(),
+            type_: type_.to_string(),
+        }
+    }
+
+    #[test]
+    fn added_module_is_compatible() {
+        let old = package(vec![]);
+        let new = package(vec![module("m")]);
+
+        let report = diff_packages(&old, &new);
+
+        assert_eq!(report.added_modules, vec!["m".to_string()]);
+        assert_eq!(report.compatibility, Compatibility::Compatible);
+    }
+
+    #[test]
+    fn removed_module_is_breaking() {
+        let old = package(vec![module("m")]);
+        let new = package(vec![]);
+
+        let report = diff_packages(&old, &new);
+
+        assert_eq!(report.removed_modules, vec!["m".to_string()]);
+        assert_eq!(report.compatibility, Compatibility::Breaking);
+    }
+
+    #[test]
+    fn reordered_struct_fields_are_breaking() {
+        let mut old_module = module("m");
+        old_module.structs.push(DecodedStruct {
+            name: "S".to_string(),
+            abilities: Vec::new(),
+            fields: vec![field("a", "u64"), field("b", "u64")],
+        });
+        let mut new_module = old_module.clone();
+        new_module.structs[0].fields = vec![field("b", "u64"), field("a", "u64")];
+
+        let old = package(vec![old_module]);
+        let new = package(vec![new_module]);
+
+        let report = diff_packages(&old, &new);
+
+        assert_eq!(report.compatibility, Compatibility::Breaking);
+        let change = &report.module_diffs[0].struct_changes[0];
+        assert!(change.description.contains("reordered fields"), "{}", change.description);
+    }
+
+    #[test]
+    fn struct_field_type_change_is_breaking() {
+        let mut old_module = module("m");
+        old_module.structs.push(DecodedStruct {
+            name: "S".to_string(),
+            abilities: Vec::new(),
+            fields: vec![field("a", "u64")],
+        });
+        let mut new_module = old_module.clone();
+        new_module.structs[0].fields = vec![field("a", "address")];
+
+        let old = package(vec![old_module]);
+        let new = package(vec![new_module]);
+
+        let report = diff_packages(&old, &new);
+
+        assert_eq!(report.compatibility, Compatibility::Breaking);
+        let change = &report.module_diffs[0].struct_changes[0];
+        assert!(change.description.contains("changed field types"), "{}", change.description);
+    }
+
+    #[test]
+    fn narrowing_public_to_friend_is_breaking() {
+        let mut old_module = module("m");
+        old_module.functions.push(function("f", "public", false));
+        let mut new_module = old_module.clone();
+        new_module.functions[0].visibility = "public(friend)".to_string();
+
+        let old = package(vec![old_module]);
+        let new = package(vec![new_module]);
+
+        assert_eq!(diff_packages(&old, &new).compatibility, Compatibility::Breaking);
+    }
+
+    #[test]
+    fn widening_friend_to_public_is_compatible() {
+        let mut old_module = module("m");
+        old_module.functions.push(function("f", "public(friend)", false));
+        let mut new_module = old_module.clone();
+        new_module.functions[0].visibility = "public".to_string();
+
+        let old = package(vec![old_module]);
+        let new = package(vec![new_module]);
+
+        assert_eq!(diff_packages(&old, &new).compatibility, Compatibility::Compatible);
+    }
+
+    #[test]
+    fn removing_entry_from_public_function_is_breaking() {
+        let mut old_module = module("m");
+        old_module.functions.push(function("f", "public", true));
+        let mut new_module = old_module.clone();
+        new_module.functions[0].is_entry = false;
+
+        let old = package(vec![old_module]);
+        let new = package(vec![new_module]);
+
+        assert_eq!(diff_packages(&old, &new).compatibility, Compatibility::Breaking);
+    }
+
+    #[test]
+    fn adding_entry_to_public_function_is_compatible() {
+        let mut old_module = module("m");
+        old_module.functions.push(function("f", "public", false));
+        let mut new_module = old_module.clone();
+        new_module.functions[0].is_entry = true;
+
+        let old = package(vec![old_module]);
+        let new = package(vec![new_module]);
+
+        assert_eq!(diff_packages(&old, &new).compatibility, Compatibility::Compatible);
+    }
+
+    #[test]
+    fn narrowing_type_param_constraints_is_breaking() {
+        let mut old_module = module("m");
+        let mut f = function("f", "public", false);
+        f.type_params = vec![DecodedTypeParam {
+            name: "T0".to_string(),
+            constraints: Vec::new(),
+        }];
+        old_module.functions.push(f);
+        let mut new_module = old_module.clone();
+        new_module.functions[0].type_params = vec![DecodedTypeParam {
+            name: "T0".to_string(),
+            constraints: vec!["copy".to_string()],
+        }];
+
+        let old = package(vec![old_module]);
+        let new = package(vec![new_module]);
+
+        assert_eq!(diff_packages(&old, &new).compatibility, Compatibility::Breaking);
+    }
+
+    #[test]
+    fn private_function_removal_is_compatible() {
+        let old_module_with_fn = {
+            let mut m = module("m");
+            m.functions.push(function("f", "private", false));
+            m
+        };
+        let new_module = module("m");
+
+        let old = package(vec![old_module_with_fn]);
+        let new = package(vec![new_module]);
+
+        assert_eq!(diff_packages(&old, &new).compatibility, Compatibility::Compatible);
+    }
+}
@@ -0,0 +1,87 @@
+//! Higher-level fetch helpers built on top of the typed [`crate::queries`].
+//!
+//! These wrap the single-page `PackageQuery` operation with the two access
+//! patterns callers actually need: one specific version (or "latest"), and
+//! every version, paging through `packageVersions` until `pageInfo` says
+//! there's nothing left.
+
+use anyhow::{bail, Result};
+use cynic::http::ReqwestExt;
+use cynic::QueryBuilder;
+
+use crate::queries::{
+    MovePackageVersion, PackageQuery, PackageQueryArgs, SuiAddress, DEFAULT_VERSIONS_PAGE_SIZE,
+};
+
+async fn run_package_query(
+    client: &reqwest::Client,
+    endpoint: &str,
+    args: PackageQueryArgs,
+) -> Result<Option<crate::queries::MovePackage>> {
+    let operation = PackageQuery::build(args);
+    let response = client.post(endpoint).run_graphql(operation).await?;
+
+    if let Some(errors) = response.errors {
+        let messages: Vec<_> = errors.into_iter().map(|e| e.message).collect();
+        bail!("GraphQL errors: {}", messages.join("; "));
+    }
+
+    Ok(response.data.and_then(|data| data.package))
+}
+
+/// Resolve `address` to a specific historical version, or to the latest
+/// version when `version` is `None`.
+pub async fn fetch_package_at_version(
+    client: &reqwest::Client,
+    endpoint: &str,
+    address: &str,
+    version: Option<u64>,
+) -> Result<Option<crate::queries::MovePackage>> {
+    let args = PackageQueryArgs {
+        address: SuiAddress::from(address),
+        version: version.map(|v| v as i32),
+        cursor: None,
+        page_size: DEFAULT_VERSIONS_PAGE_SIZE,
+    };
+
+    run_package_query(client, endpoint, args).await
+}
+
+/// Page through every `packageVersions` entry for `address`, following
+/// `pageInfo.endCursor` until `pageInfo.hasNextPage` is `false`.
+pub async fn fetch_all_versions(
+    client: &reqwest::Client,
+    endpoint: &str,
+    address: &str,
+) -> Result<Vec<MovePackageVersion>> {
+    let mut all_versions = Vec::new();
+    let mut cursor: Option<String> = None;
+
+    loop {
+        let args = PackageQueryArgs {
+            address: SuiAddress::from(address),
+            version: None,
+            cursor: cursor.take(),
+            page_size: DEFAULT_VERSIONS_PAGE_SIZE,
+        };
+
+        let Some(package) = run_package_query(client, endpoint, args).await? else {
+            break;
+        };
+
+        let page_info = package.package_versions.page_info;
+        all_versions.extend(package.package_versions.nodes);
+
+        if !page_info.has_next_page {
+            break;
+        }
+        cursor = page_info.end_cursor;
+        if cursor.is_none() {
+            // Server claims more pages exist but gave us nothing to page
+            // with; stop rather than looping on the same request forever.
+            break;
+        }
+    }
+
+    Ok(all_versions)
+}
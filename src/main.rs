@@ -1,197 +1,183 @@
-use anyhow::Result;
-use serde::{Deserialize, Serialize};
+use anyhow::{Context, Result};
+use clap::Parser;
 use serde_json::json;
-use std::collections::HashMap;
 
-#[derive(Debug, Serialize, Deserialize)]
-struct GraphQLRequest {
-    query: String,
-    variables: HashMap<String, serde_json::Value>,
-}
+mod cli;
+mod decode;
+mod diff;
+mod fetch;
+mod queries;
+mod registry;
+mod schema;
 
-#[derive(Debug, Deserialize)]
-struct GraphQLResponse {
-    data: Option<serde_json::Value>,
-    errors: Option<Vec<GraphQLError>>,
-}
+use cli::{Cli, Command};
+use diff::{Compatibility, PackageDiff};
 
-#[derive(Debug, Deserialize)]
-struct GraphQLError {
-    message: String,
-}
+async fn run_fetch(client: &reqwest::Client, endpoint: &str, address_or_name: &str) -> Result<()> {
+    let Some(package) = registry::resolve_and_fetch(client, endpoint, address_or_name).await?
+    else {
+        println!("Package not found: {}", address_or_name);
+        return Ok(());
+    };
 
-#[derive(Debug, Deserialize)]
-struct PackageData {
-    address: String,
-    version: u64,
-    #[serde(rename = "packageVersions")]
-    package_versions: Option<PackageVersions>,
-}
+    let all_versions = fetch::fetch_all_versions(client, endpoint, &package.address.0).await?;
 
-#[derive(Debug, Deserialize)]
-struct PackageVersions {
-    nodes: Vec<PackageVersionNode>,
-}
+    let response_json = json!({
+        "status": "success",
+        "query": {
+            "package_address": package.address.0,
+            "timestamp": chrono::Utc::now().to_rfc3339(),
+            "endpoint": endpoint
+        },
+        "data": {
+            "address": package.address.0,
+            "version": package.version,
+            "packageVersions": all_versions.iter().map(|n| json!({
+                "address": n.address.0,
+                "version": n.version,
+                "packageBcs": n.package_bcs.as_ref().map(|b| &b.0),
+                "moduleBcs": n.module_bcs.as_ref().map(|ms| ms.iter().map(|m| &m.0).collect::<Vec<_>>()),
+            })).collect::<Vec<_>>()
+        }
+    });
 
-#[derive(Debug, Deserialize)]
-struct PackageVersionNode {
-    address: String,
-    version: u64,
-    #[serde(rename = "packageBcs")]
-    package_bcs: Option<String>,
-    #[serde(rename = "moduleBcs")]
-    module_bcs: Option<Vec<String>>,
-}
+    std::fs::write("response.json", serde_json::to_string_pretty(&response_json)?)?;
 
-async fn query_sui_package(package_address: &str) -> Result<()> {
-    let client = reqwest::Client::new();
-    
-    // The GraphQL query
-    let query = r#"
-        query PackageQuery($address: SuiAddress!) {
-            package(address: $address) {
-                address
-                version
-                packageVersions(first: 50) {
-                    nodes {
-                        address
-                        version
-                        packageBcs
-                    }
-                }
+    println!("=== Package Information ===");
+    println!("Address: {}", package.address.0);
+    println!("Current Version: {}", package.version);
+
+    println!("\n=== Package Versions ({} found) ===", all_versions.len());
+
+    for (i, version) in all_versions.iter().enumerate() {
+        println!("\n--- Version {} ---", i + 1);
+        println!("  Address: {}", version.address.0);
+        println!("  Version Number: {}", version.version);
+
+        if let Some(package_bcs) = &version.package_bcs {
+            println!("  Package BCS Length: {} characters", package_bcs.0.len());
+            if package_bcs.0.len() > 100 {
+                println!("  Package BCS Preview: {}...", &package_bcs.0[..100]);
+            } else {
+                println!("  Package BCS: {}", package_bcs.0);
             }
+        } else {
+            println!("  Package BCS: None");
         }
-    "#;
-    
-    // Create variables
-    let mut variables = HashMap::new();
-    variables.insert("address".to_string(), json!(package_address));
-    
-    // Create the request
-    let request = GraphQLRequest {
-        query: query.to_string(),
-        variables,
-    };
-    
-    // Send the request to Sui mainnet GraphQL endpoint
-    let response = client
-        .post("https://sui-mainnet.mystenlabs.com/graphql")
-        .header("Content-Type", "application/json")
-        .json(&request)
-        .send()
-        .await?;
-    
-    // Check if the request was successful
-    if !response.status().is_success() {
-        println!("HTTP Error: {}", response.status());
-        let error_text = response.text().await?;
-        println!("Error response: {}", error_text);
-        return Ok(());
-    }
-    
-    // Parse the response
-    let graphql_response: GraphQLResponse = response.json().await?;
-    
-    // Handle errors
-    if let Some(errors) = graphql_response.errors {
-        println!("GraphQL Errors:");
-        for error in errors {
-            println!("  - {}", error.message);
-        }
-        return Ok(());
-    }
-    
-    // Process the data
-    if let Some(data) = graphql_response.data {
-        if let Some(package_data) = data.get("package") {
-            if package_data.is_null() {
-                println!("Package not found at address: {}", package_address);
-                return Ok(());
-            }
 
-            let response_json = json!({
-                "status": "success",
-                "query": {
-                    "package_address": package_address,
-                    "timestamp": chrono::Utc::now().to_rfc3339(),
-                    "endpoint": "https://sui-mainnet.mystenlabs.com/graphql"
-                },
-                "data": data
-            });
-            
-            std::fs::write("response.json", serde_json::to_string_pretty(&response_json)?)?;
-            
-            // Try to deserialize the package data
-            match serde_json::from_value::<PackageData>(package_data.clone()) {
-                Ok(package) => {
-                    println!("=== Package Information ===");
-                    println!("Address: {}", package.address);
-                    println!("Current Version: {}", package.version);
-                    
-                    if let Some(versions) = package.package_versions {
-                        println!("\n=== Package Versions ({} found) ===", versions.nodes.len());
-                        
-                        for (i, version) in versions.nodes.iter().enumerate() {
-                            println!("\n--- Version {} ---", i + 1);
-                            println!("  Address: {}", version.address);
-                            println!("  Version Number: {}", version.version);
-                            
-                            if let Some(package_bcs) = &version.package_bcs {
-                                println!("  Package BCS Length: {} characters", package_bcs.len());
-                                // Show first 100 characters of BCS data
-                                if package_bcs.len() > 100 {
-                                    println!("  Package BCS Preview: {}...", &package_bcs[..100]);
-                                } else {
-                                    println!("  Package BCS: {}", package_bcs);
-                                }
-                            } else {
-                                println!("  Package BCS: None");
-                            }
-                            
-                            if let Some(modules) = &version.module_bcs {
-                                println!("  Number of Modules: {}", modules.len());
-                                for (j, module) in modules.iter().enumerate() {
-                                    println!("    Module {}: {} characters", j + 1, module.len());
-                                    // Show first 50 characters of each module
-                                    if module.len() > 50 {
-                                        println!("      Preview: {}...", &module[..50]);
-                                    }
-                                }
-                            } else {
-                                println!("  Module BCS: None");
-                            }
-                        }
-                    } else {
-                        println!("No package versions found");
+        if version.module_bcs.is_some() {
+            match decode::decode_package_version(&version.address.0, version.version, version) {
+                Ok(decoded) => {
+                    println!("  Number of Modules: {}", decoded.modules.len());
+                    for module in &decoded.modules {
+                        println!(
+                            "    Module `{}`: {} structs, {} functions, {} friends",
+                            module.name,
+                            module.structs.len(),
+                            module.functions.len(),
+                            module.friends.len()
+                        );
                     }
                 }
-                Err(e) => {
-                    println!("Failed to parse package data: {}", e);
-                    println!("Raw data: {}", serde_json::to_string_pretty(package_data)?);
-                }
+                Err(e) => println!("  Failed to decode modules: {}", e),
             }
         } else {
-            println!("No package data in response");
-            println!("Full response: {}", serde_json::to_string_pretty(&data)?);
+            println!("  Module BCS: None");
         }
-    } else {
-        println!("No data in response");
     }
-    
+
+    Ok(())
+}
+
+async fn run_diff(
+    client: &reqwest::Client,
+    endpoint: &str,
+    address_or_name: &str,
+    from: u64,
+    to: u64,
+) -> Result<()> {
+    let (address, _) = if registry::is_registry_name(address_or_name) {
+        registry::resolve_name(client, address_or_name).await?
+    } else {
+        (address_or_name.to_string(), None)
+    };
+
+    let all_versions = fetch::fetch_all_versions(client, endpoint, &address).await?;
+
+    let from_node = all_versions
+        .iter()
+        .find(|v| v.version as u64 == from)
+        .with_context(|| format!("version {from} not found for {address}"))?;
+    let to_node = all_versions
+        .iter()
+        .find(|v| v.version as u64 == to)
+        .with_context(|| format!("version {to} not found for {address}"))?;
+
+    let old_package = decode::decode_package_version(&address, from_node.version, from_node)?;
+    let new_package = decode::decode_package_version(&address, to_node.version, to_node)?;
+
+    print_diff(&address, from, to, &diff::diff_packages(&old_package, &new_package));
+
     Ok(())
 }
 
+fn print_diff(address: &str, from: u64, to: u64, report: &PackageDiff) {
+    println!("=== Diff: {address} v{from} -> v{to} ===");
+    println!(
+        "Overall compatibility: {}",
+        match report.compatibility {
+            Compatibility::Compatible => "compatible",
+            Compatibility::Breaking => "BREAKING",
+        }
+    );
+
+    for module in &report.added_modules {
+        println!("\n+ module {module} (added)");
+    }
+    for module in &report.removed_modules {
+        println!("\n- module {module} (removed, BREAKING)");
+    }
+
+    for module_diff in &report.module_diffs {
+        println!("\n--- module {} ---", module_diff.name);
+        for change in &module_diff.function_changes {
+            let marker = match change.compatibility {
+                Compatibility::Compatible => " ",
+                Compatibility::Breaking => "!",
+            };
+            println!("  [{marker}] fn {}: {}", change.name, change.description);
+        }
+        for change in &module_diff.struct_changes {
+            let marker = match change.compatibility {
+                Compatibility::Compatible => " ",
+                Compatibility::Breaking => "!",
+            };
+            println!("  [{marker}] struct {}: {}", change.name, change.description);
+        }
+        for change in &module_diff.friend_changes {
+            println!("  [ ] {change}");
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
-    println!("Querying Sui Package...");
-    
-    // The package address you want to query
-    let package_address = "0xc33c3e937e5aa2009cc0c3fdb3f345a0c3193d4ee663ffc601fe8b894fbc4ba6";
-    
-    match query_sui_package(package_address).await {
-        Ok(_) => println!("\nQuery completed successfully!"),
-        Err(e) => println!("Error occurred: {}", e),
+    let cli = Cli::parse();
+    let endpoint = cli.resolved_endpoint();
+    let client = reqwest::Client::new();
+
+    let result = match &cli.command {
+        Command::Fetch { address_or_name } => run_fetch(&client, &endpoint, address_or_name).await,
+        Command::Diff {
+            address_or_name,
+            from,
+            to,
+        } => run_diff(&client, &endpoint, address_or_name, *from, *to).await,
+    };
+
+    if let Err(e) = &result {
+        println!("Error occurred: {}", e);
     }
-    
-    Ok(())
-}
\ No newline at end of file
+
+    result
+}
@@ -0,0 +1,75 @@
+//! Typed GraphQL operations, checked against [`crate::schema`] at compile time.
+
+use crate::schema;
+
+#[derive(cynic::Scalar, Debug, Clone)]
+#[cynic(graphql_type = "SuiAddress")]
+pub struct SuiAddress(pub String);
+
+impl From<&str> for SuiAddress {
+    fn from(address: &str) -> Self {
+        SuiAddress(address.to_string())
+    }
+}
+
+#[derive(cynic::Scalar, Debug, Clone)]
+#[cynic(graphql_type = "Base64")]
+pub struct Base64(pub String);
+
+/// Page size the helpers in [`crate::fetch`] pass as `PackageQueryArgs::page_size`.
+pub const DEFAULT_VERSIONS_PAGE_SIZE: i32 = 50;
+
+#[derive(cynic::QueryVariables, Debug)]
+pub struct PackageQueryArgs {
+    pub address: SuiAddress,
+    pub version: Option<i32>,
+    pub cursor: Option<String>,
+    pub page_size: i32,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Query", variables = "PackageQueryArgs")]
+pub struct PackageQuery {
+    #[arguments(address: $address, version: $version)]
+    pub package: Option<MovePackage>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "MovePackage", variables = "PackageQueryArgs")]
+pub struct MovePackage {
+    pub address: SuiAddress,
+    pub version: i32,
+    #[cynic(rename = "packageBcs")]
+    pub package_bcs: Option<Base64>,
+    #[cynic(rename = "packageVersions")]
+    #[arguments(first: $page_size, after: $cursor)]
+    pub package_versions: MovePackageConnection,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "MovePackageConnection")]
+pub struct MovePackageConnection {
+    #[cynic(rename = "pageInfo")]
+    pub page_info: PageInfo,
+    pub nodes: Vec<MovePackageVersion>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "PageInfo")]
+pub struct PageInfo {
+    #[cynic(rename = "hasNextPage")]
+    pub has_next_page: bool,
+    #[cynic(rename = "endCursor")]
+    pub end_cursor: Option<String>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "MovePackageVersion")]
+pub struct MovePackageVersion {
+    pub address: SuiAddress,
+    pub version: i32,
+    #[cynic(rename = "packageBcs")]
+    pub package_bcs: Option<Base64>,
+    #[cynic(rename = "moduleBcs")]
+    pub module_bcs: Option<Vec<Base64>>,
+}
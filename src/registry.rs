@@ -0,0 +1,162 @@
+//! Resolve human-readable Move Registry (MVR) / DotMove names like
+//! `app@org` to the on-chain address (and optionally version) they point
+//! at, mirroring Sui's DotMove external resolver
+//! (`move_registry_data_loader`) so callers can pass a name anywhere an
+//! address is accepted.
+
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::fetch;
+use crate::queries::MovePackage;
+
+/// The real MVR/DotMove resolution endpoint only exposes bulk lookup, even
+/// for a single name: `POST /v1/resolution/bulk` with `{"names": [...]}`,
+/// returning `{"resolution": {"<name>": {...}}}` keyed by the names in the
+/// request, the same lookup DotMove's `move_registry_data_loader` performs.
+const MVR_RESOLUTION_ENDPOINT: &str = "https://mainnet.mvr.mystenlabs.com/v1/resolution/bulk";
+
+#[derive(Debug, Serialize)]
+struct MvrBulkResolutionRequest<'a> {
+    names: Vec<&'a str>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MvrBulkResolutionResponse {
+    resolution: HashMap<String, MvrResolution>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MvrResolution {
+    package_address: String,
+    version: Option<u64>,
+}
+
+struct ParsedName {
+    name: String,
+    version: Option<u64>,
+}
+
+/// A registry name looks like `name@org` or `name@org/version`; a raw
+/// on-chain address starts with `0x` and never contains `@`.
+pub fn is_registry_name(input: &str) -> bool {
+    !input.starts_with("0x") && input.contains('@')
+}
+
+/// Only the trailing segment after the *last* `/` is ever a version, and
+/// only when it actually parses as one: MVR/DotMove names use `/` for
+/// namespace/app separation too (e.g. `@mysten/core`), so a non-numeric
+/// trailing segment is part of the name, not a silently-dropped version.
+fn parse_name(input: &str) -> ParsedName {
+    match input.rsplit_once('/') {
+        Some((name, version)) if version.parse::<u64>().is_ok() => ParsedName {
+            name: name.to_string(),
+            version: version.parse().ok(),
+        },
+        _ => ParsedName {
+            name: input.to_string(),
+            version: None,
+        },
+    }
+}
+
+/// Resolve `name@org[/version]` to an on-chain package address and
+/// version via the Move Registry, the same lookup DotMove's external
+/// resolver performs.
+pub async fn resolve_name(
+    client: &reqwest::Client,
+    name_or_path: &str,
+) -> Result<(String, Option<u64>)> {
+    let parsed = parse_name(name_or_path);
+
+    let request = MvrBulkResolutionRequest {
+        names: vec![parsed.name.as_str()],
+    };
+
+    let response: MvrBulkResolutionResponse = client
+        .post(MVR_RESOLUTION_ENDPOINT)
+        .json(&request)
+        .send()
+        .await?
+        .error_for_status()
+        .context("move registry lookup failed")?
+        .json()
+        .await
+        .context("move registry returned an unexpected response shape")?;
+
+    let resolution = response
+        .resolution
+        .get(&parsed.name)
+        .with_context(|| format!("move registry has no resolution for `{}`", parsed.name))?;
+
+    // An explicit `/version` in the input wins over whatever the registry
+    // considers "current".
+    Ok((resolution.package_address.clone(), parsed.version.or(resolution.version)))
+}
+
+/// Accepts either a `0x...` address or a `name@org[/version]` registry
+/// name, resolving the latter before issuing the package query. This lets
+/// every caller of `fetch_package_at_version` take a name or an address
+/// interchangeably.
+pub async fn resolve_and_fetch(
+    client: &reqwest::Client,
+    endpoint: &str,
+    name_or_address: &str,
+) -> Result<Option<MovePackage>> {
+    let (address, version) = if is_registry_name(name_or_address) {
+        resolve_name(client, name_or_address).await?
+    } else {
+        (name_or_address.to_string(), None)
+    };
+
+    fetch::fetch_package_at_version(client, endpoint, &address, version).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_trailing_numeric_version() {
+        let parsed = parse_name("core@mysten/3");
+        assert_eq!(parsed.name, "core@mysten");
+        assert_eq!(parsed.version, Some(3));
+    }
+
+    #[test]
+    fn keeps_non_numeric_trailing_segment_as_part_of_the_name() {
+        let parsed = parse_name("@mysten/core");
+        assert_eq!(parsed.name, "@mysten/core");
+        assert_eq!(parsed.version, None);
+    }
+
+    #[test]
+    fn name_without_slash_has_no_version() {
+        let parsed = parse_name("core@mysten");
+        assert_eq!(parsed.name, "core@mysten");
+        assert_eq!(parsed.version, None);
+    }
+
+    /// Shape of a real `/v1/resolution/bulk` response body: a map of the
+    /// requested names to their resolution, not the flat single-object body
+    /// the first draft of this module assumed.
+    #[test]
+    fn decodes_bulk_resolution_response_body() {
+        let body = r#"{
+            "resolution": {
+                "core@mysten": {
+                    "package_address": "0xabc123",
+                    "version": 4
+                }
+            }
+        }"#;
+
+        let response: MvrBulkResolutionResponse = serde_json::from_str(body).unwrap();
+        let resolution = response.resolution.get("core@mysten").unwrap();
+
+        assert_eq!(resolution.package_address, "0xabc123");
+        assert_eq!(resolution.version, Some(4));
+    }
+}
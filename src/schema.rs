@@ -0,0 +1,9 @@
+//! Generated GraphQL schema bindings.
+//!
+//! `cynic::use_schema!` parses `schema.graphql` at compile time and emits a
+//! module of marker types that the `QueryFragment`/`QueryVariables` structs
+//! in `queries.rs` are checked against, so a field that doesn't exist (or
+//! has the wrong type) is a compile error rather than a runtime GraphQL
+//! error.
+
+cynic::use_schema!("schema.graphql");